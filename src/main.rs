@@ -1,20 +1,32 @@
 use std::{
+    collections::HashMap,
     env,
     fs::File,
     io::{BufRead, BufReader},
     path::PathBuf,
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
 use chrono::{DateTime, Local};
+use encoding_rs::{GB18030, UTF_16BE, UTF_16LE, UTF_8};
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 
 const URL: &str = "https://air.cnemc.cn:18007/CityData/GetAQIDataPublishLive?cityName=%E5%B9%BF%E5%B7%9E%E5%B8%82";
 
 // Ignore-alert station names (exact match)
-const IGNORE_POSITION_NAMES: [&str; 2] = ["å¸½å³°å±±", "å¸½å³°å±±æ£®æ—å…¬å›­"];
+const IGNORE_POSITION_NAMES: [&str; 2] = ["帽峰山", "帽峰山森林公园"];
+
+// Name of the persistent alert-state file, kept next to the .env in exe_dir().
+const STATE_FILE_NAME: &str = "alert_state.json";
+
+// Default minimum time between re-sends for a station that is still alerting.
+const DEFAULT_RENOTIFY_COOLDOWN_SECS: u64 = 3600;
+
+// Default polling interval for --watch mode.
+const DEFAULT_WATCH_INTERVAL_SECS: u64 = 300;
 
 #[derive(Debug, Clone, Deserialize)]
 struct AQIData {
@@ -59,6 +71,130 @@ struct AQIData {
 struct Config {
     webhook_key: String,
     dingtalk_access_token: String,
+    telegram_bot_token: String,
+    telegram_chat_id: String,
+    // URL of an HTTP endpoint that accepts POSTed JSON alerts, not a
+    // native AMQP/Kafka/MQTT broker address — see `MessageQueueNotifier`.
+    mq_broker_url: String,
+    mq_topic: String,
+    renotify_cooldown_secs: u64,
+    thresholds: Thresholds,
+}
+
+/// Per-pollutant alerting limits. AQI, PM2.5 and PM10 default to the
+/// IAQI 200 breakpoint of the national AQI standard; O3, NO2, SO2 and CO
+/// default to their IAQI 100 breakpoints. Operators can override any of
+/// them from the .env file.
+#[derive(Debug, Clone, Copy)]
+struct Thresholds {
+    aqi: f64,
+    pm25: f64,
+    pm10: f64,
+    o3: f64,
+    no2: f64,
+    so2: f64,
+    co: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            aqi: 200.0,
+            pm25: 150.0,
+            pm10: 250.0,
+            o3: 200.0,
+            no2: 200.0,
+            so2: 500.0,
+            co: 4.0,
+        }
+    }
+}
+
+/// The worst single pollutant by which a station exceeded its configured
+/// threshold, used to build the "exceedance" alert section.
+#[derive(Debug, Clone)]
+struct StationExceedance {
+    station_name: String,
+    quality: String,
+    factor: &'static str,
+    value: f64,
+    limit: f64,
+}
+
+/// A channel alerts can be published to. Each notifier owns everything it
+/// needs (HTTP client, credentials) so `main` can just iterate over a
+/// `Vec<Box<dyn Notifier>>` instead of hard-wiring an `if` per backend.
+#[async_trait]
+trait Notifier: Send + Sync {
+    /// Human-readable name used in log output.
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, report: &AlertReport) -> Result<()>;
+
+    async fn send_recovery(&self, report: &RecoveryReport) -> Result<()>;
+}
+
+/// The set of currently-problematic stations, any threshold exceedances,
+/// and the formatted time they were observed at, ready to be rendered by
+/// any `Notifier`.
+#[derive(Debug, Clone)]
+struct AlertReport {
+    problem_stations: Vec<AQIData>,
+    exceedances: Vec<StationExceedance>,
+    formatted_time: String,
+}
+
+impl AlertReport {
+    fn new(problem_stations: Vec<AQIData>, exceedances: Vec<StationExceedance>) -> Self {
+        let formatted_time = format_time(&problem_stations);
+        Self {
+            problem_stations,
+            exceedances,
+            formatted_time,
+        }
+    }
+}
+
+/// The station names whose missing-data or exceedance alerts have cleared
+/// since the last pass, ready to be rendered by any `Notifier`.
+#[derive(Debug, Clone)]
+struct RecoveryReport {
+    recovered_stations: Vec<String>,
+    recovered_exceedances: Vec<String>,
+}
+
+impl RecoveryReport {
+    fn new(recovered_stations: Vec<String>, recovered_exceedances: Vec<String>) -> Self {
+        Self {
+            recovered_stations,
+            recovered_exceedances,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.recovered_stations.is_empty() && self.recovered_exceedances.is_empty()
+    }
+}
+
+/// Which stations are currently considered "alerting", and when each one
+/// was last notified about, so repeated --watch passes don't re-spam the
+/// same broken station every run.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct AlertState {
+    stations: HashMap<String, StationAlertState>,
+    #[serde(default)]
+    exceeding: HashMap<String, StationAlertState>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StationAlertState {
+    last_notified_epoch: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RunOptions {
+    watch: bool,
+    interval: Duration,
 }
 
 #[derive(Debug, Serialize)]
@@ -100,6 +236,26 @@ struct DingTalkAt {
     is_at_all: bool,
 }
 
+fn parse_args() -> RunOptions {
+    let mut watch = false;
+    let mut interval = Duration::from_secs(DEFAULT_WATCH_INTERVAL_SECS);
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--watch" => watch = true,
+            "--interval" => {
+                if let Some(value) = args.next().and_then(|v| v.parse::<u64>().ok()) {
+                    interval = Duration::from_secs(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    RunOptions { watch, interval }
+}
+
 fn exe_dir() -> Result<PathBuf> {
     let exe = env::current_exe().context("Failed to get executable path")?;
     Ok(exe
@@ -125,9 +281,51 @@ fn read_config_from_env(env_path: PathBuf) -> Result<Config> {
             config.webhook_key = value.trim().to_string();
         } else if let Some(value) = line.strip_prefix("DINGTALK_ACCESS_TOKEN=") {
             config.dingtalk_access_token = value.trim().to_string();
+        } else if let Some(value) = line.strip_prefix("TELEGRAM_BOT_TOKEN=") {
+            config.telegram_bot_token = value.trim().to_string();
+        } else if let Some(value) = line.strip_prefix("TELEGRAM_CHAT_ID=") {
+            config.telegram_chat_id = value.trim().to_string();
+        } else if let Some(value) = line.strip_prefix("MQ_BROKER_URL=") {
+            config.mq_broker_url = value.trim().to_string();
+        } else if let Some(value) = line.strip_prefix("MQ_TOPIC=") {
+            config.mq_topic = value.trim().to_string();
+        } else if let Some(value) = line.strip_prefix("RENOTIFY_COOLDOWN_SECS=") {
+            config.renotify_cooldown_secs = value.trim().parse().unwrap_or(0);
+        } else if let Some(value) = line.strip_prefix("THRESHOLD_AQI=") {
+            if let Ok(v) = value.trim().parse() {
+                config.thresholds.aqi = v;
+            }
+        } else if let Some(value) = line.strip_prefix("THRESHOLD_PM25=") {
+            if let Ok(v) = value.trim().parse() {
+                config.thresholds.pm25 = v;
+            }
+        } else if let Some(value) = line.strip_prefix("THRESHOLD_PM10=") {
+            if let Ok(v) = value.trim().parse() {
+                config.thresholds.pm10 = v;
+            }
+        } else if let Some(value) = line.strip_prefix("THRESHOLD_O3=") {
+            if let Ok(v) = value.trim().parse() {
+                config.thresholds.o3 = v;
+            }
+        } else if let Some(value) = line.strip_prefix("THRESHOLD_NO2=") {
+            if let Ok(v) = value.trim().parse() {
+                config.thresholds.no2 = v;
+            }
+        } else if let Some(value) = line.strip_prefix("THRESHOLD_SO2=") {
+            if let Ok(v) = value.trim().parse() {
+                config.thresholds.so2 = v;
+            }
+        } else if let Some(value) = line.strip_prefix("THRESHOLD_CO=") {
+            if let Ok(v) = value.trim().parse() {
+                config.thresholds.co = v;
+            }
         }
     }
 
+    if config.renotify_cooldown_secs == 0 {
+        config.renotify_cooldown_secs = DEFAULT_RENOTIFY_COOLDOWN_SECS;
+    }
+
     Ok(config)
 }
 
@@ -136,9 +334,9 @@ fn get_config() -> Result<Config> {
 
     match read_config_from_env(env_path) {
         Ok(cfg) => {
-            if cfg.webhook_key.is_empty() && cfg.dingtalk_access_token.is_empty() {
+            if !has_any_notifier_configured(&cfg) {
                 return Err(anyhow!(
-                    "No webhook configuration found in .env file (WEBHOOK_KEY / DINGTALK_ACCESS_TOKEN)"
+                    "No notifier configuration found in .env file (WEBHOOK_KEY / DINGTALK_ACCESS_TOKEN / TELEGRAM_BOT_TOKEN+TELEGRAM_CHAT_ID / MQ_BROKER_URL+MQ_TOPIC)"
                 ));
             }
             Ok(cfg)
@@ -148,10 +346,19 @@ fn get_config() -> Result<Config> {
             let cfg = Config {
                 webhook_key: env::var("WEBHOOK_KEY").unwrap_or_default(),
                 dingtalk_access_token: env::var("DINGTALK_ACCESS_TOKEN").unwrap_or_default(),
+                telegram_bot_token: env::var("TELEGRAM_BOT_TOKEN").unwrap_or_default(),
+                telegram_chat_id: env::var("TELEGRAM_CHAT_ID").unwrap_or_default(),
+                mq_broker_url: env::var("MQ_BROKER_URL").unwrap_or_default(),
+                mq_topic: env::var("MQ_TOPIC").unwrap_or_default(),
+                renotify_cooldown_secs: env::var("RENOTIFY_COOLDOWN_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(DEFAULT_RENOTIFY_COOLDOWN_SECS),
+                thresholds: thresholds_from_env(),
             };
-            if cfg.webhook_key.is_empty() && cfg.dingtalk_access_token.is_empty() {
+            if !has_any_notifier_configured(&cfg) {
                 return Err(anyhow!(
-                    "No webhook configuration found in .env file or environment variables"
+                    "No notifier configuration found in .env file or environment variables"
                 ));
             }
             Ok(cfg)
@@ -159,20 +366,246 @@ fn get_config() -> Result<Config> {
     }
 }
 
+fn thresholds_from_env() -> Thresholds {
+    let mut thresholds = Thresholds::default();
+    if let Some(v) = env::var("THRESHOLD_AQI").ok().and_then(|v| v.parse().ok()) {
+        thresholds.aqi = v;
+    }
+    if let Some(v) = env::var("THRESHOLD_PM25").ok().and_then(|v| v.parse().ok()) {
+        thresholds.pm25 = v;
+    }
+    if let Some(v) = env::var("THRESHOLD_PM10").ok().and_then(|v| v.parse().ok()) {
+        thresholds.pm10 = v;
+    }
+    if let Some(v) = env::var("THRESHOLD_O3").ok().and_then(|v| v.parse().ok()) {
+        thresholds.o3 = v;
+    }
+    if let Some(v) = env::var("THRESHOLD_NO2").ok().and_then(|v| v.parse().ok()) {
+        thresholds.no2 = v;
+    }
+    if let Some(v) = env::var("THRESHOLD_SO2").ok().and_then(|v| v.parse().ok()) {
+        thresholds.so2 = v;
+    }
+    if let Some(v) = env::var("THRESHOLD_CO").ok().and_then(|v| v.parse().ok()) {
+        thresholds.co = v;
+    }
+    thresholds
+}
+
+fn has_any_notifier_configured(cfg: &Config) -> bool {
+    !cfg.webhook_key.is_empty()
+        || !cfg.dingtalk_access_token.is_empty()
+        || (!cfg.telegram_bot_token.is_empty() && !cfg.telegram_chat_id.is_empty())
+        || (!cfg.mq_broker_url.is_empty() && !cfg.mq_topic.is_empty())
+}
+
+/// Builds the list of notifiers implied by `config`, skipping any backend
+/// whose credentials are not fully set.
+fn build_notifiers(client: &reqwest::Client, config: &Config) -> Vec<Box<dyn Notifier>> {
+    let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+
+    if !config.webhook_key.is_empty() {
+        notifiers.push(Box::new(WechatWorkNotifier {
+            client: client.clone(),
+            webhook_key: config.webhook_key.clone(),
+        }));
+    }
+
+    if !config.dingtalk_access_token.is_empty() {
+        notifiers.push(Box::new(DingTalkNotifier {
+            client: client.clone(),
+            access_token: config.dingtalk_access_token.clone(),
+        }));
+    }
+
+    if !config.telegram_bot_token.is_empty() && !config.telegram_chat_id.is_empty() {
+        notifiers.push(Box::new(TelegramNotifier {
+            client: client.clone(),
+            bot_token: config.telegram_bot_token.clone(),
+            chat_id: config.telegram_chat_id.clone(),
+        }));
+    }
+
+    if !config.mq_broker_url.is_empty() && !config.mq_topic.is_empty() {
+        notifiers.push(Box::new(MessageQueueNotifier {
+            client: client.clone(),
+            broker_url: config.mq_broker_url.clone(),
+            topic: config.mq_topic.clone(),
+        }));
+    }
+
+    notifiers
+}
+
+// The CityData endpoint (like many Chinese government data feeds) often
+// serves GB18030/GBK-encoded text rather than UTF-8, so the raw bytes must
+// be sniffed and decoded before handing them to serde_json.
+fn decode_response_body(bytes: &[u8]) -> String {
+    let (bytes, encoding) = if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        (rest, UTF_8)
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        (rest, UTF_16BE)
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        (rest, UTF_16LE)
+    } else {
+        (bytes, UTF_8)
+    };
+
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        if encoding == UTF_8 {
+            return text.to_string();
+        }
+    }
+
+    let (text, _, had_errors) = encoding.decode(bytes);
+    if !had_errors {
+        return text.into_owned();
+    }
+
+    let (text, _, _) = GB18030.decode(bytes);
+    text.into_owned()
+}
+
+// Max attempts for send_with_retry, including the initial try.
+const MAX_RETRY_ATTEMPTS: u32 = 5;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+const MAX_REDIRECTS: u32 = 5;
+
+fn jittered(delay: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    delay + Duration::from_millis((nanos % 250) as u64)
+}
+
+fn backoff_delay(attempt: u32) -> Duration {
+    let factor = 1u32 << attempt.saturating_sub(1).min(6);
+    jittered(RETRY_BASE_DELAY.saturating_mul(factor))
+}
+
+fn retry_after_delay(resp: &reqwest::Response) -> Option<Duration> {
+    resp.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+fn resolve_url(base: &str, target: &str) -> Option<String> {
+    reqwest::Url::parse(base)
+        .ok()?
+        .join(target)
+        .ok()
+        .map(|u| u.to_string())
+}
+
+// Parses an RFC 8288 `Link` header for a `rel="next"` target, e.g.
+// `<https://example.com/next>; rel="next"`.
+fn parse_link_next(value: &str) -> Option<String> {
+    value.split(',').find_map(|part| {
+        let (url_part, rest) = part.trim().split_once('>')?;
+        let url = url_part.trim_start_matches('<').trim();
+        if rest.contains("rel=\"next\"") || rest.contains("rel=next") {
+            Some(url.to_string())
+        } else {
+            None
+        }
+    })
+}
+
+fn next_redirect_url(resp: &reqwest::Response, current_url: &str) -> Option<String> {
+    if let Some(location) = resp
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|v| v.to_str().ok())
+    {
+        return resolve_url(current_url, location);
+    }
+
+    let link = resp
+        .headers()
+        .get(reqwest::header::LINK)
+        .and_then(|v| v.to_str().ok())?;
+    resolve_url(current_url, &parse_link_next(link)?)
+}
+
+/// Sends a request, rebuilding it fresh from `build` on every attempt
+/// (a `reqwest::Request` can only be sent once). Retries connection
+/// errors, timeouts, HTTP 429 and 5xx with bounded exponential backoff
+/// and jitter (honoring `Retry-After` on 429), and manually follows 3xx
+/// redirects via `Location` or an RFC 8288 `Link` header so a webhook
+/// gateway can hand us off to a different publish endpoint.
+///
+/// Any other status (e.g. 2xx, or a non-retryable 4xx) is returned as-is
+/// for the caller to interpret.
+async fn send_with_retry<F>(
+    client: &reqwest::Client,
+    initial_url: &str,
+    build: F,
+) -> Result<reqwest::Response>
+where
+    F: Fn(&str) -> reqwest::RequestBuilder,
+{
+    let mut url = initial_url.to_string();
+    let mut attempt = 0u32;
+    let mut redirects = 0u32;
+
+    loop {
+        let request = build(&url).build().context("Failed to build request")?;
+        let client_result = client.execute(request).await;
+
+        let resp = match client_result {
+            Ok(resp) => resp,
+            Err(err) => {
+                attempt += 1;
+                if attempt >= MAX_RETRY_ATTEMPTS || !(err.is_connect() || err.is_timeout()) {
+                    return Err(err).context("Request failed");
+                }
+                tokio::time::sleep(backoff_delay(attempt)).await;
+                continue;
+            }
+        };
+
+        let status = resp.status();
+
+        if status.is_redirection() && redirects < MAX_REDIRECTS {
+            if let Some(next_url) = next_redirect_url(&resp, &url) {
+                redirects += 1;
+                url = next_url;
+                continue;
+            }
+        }
+
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            attempt += 1;
+            if attempt >= MAX_RETRY_ATTEMPTS {
+                return Ok(resp);
+            }
+            let delay = retry_after_delay(&resp).unwrap_or_else(|| backoff_delay(attempt));
+            tokio::time::sleep(delay).await;
+            continue;
+        }
+
+        return Ok(resp);
+    }
+}
+
 async fn fetch_aqi_data(client: &reqwest::Client) -> Result<Vec<AQIData>> {
-    let resp = client
-        .get(URL)
-        .timeout(Duration::from_secs(30))
-        .send()
-        .await
-        .context("Failed to fetch data")?;
+    let resp = send_with_retry(client, URL, |url| {
+        client.get(url).timeout(Duration::from_secs(30))
+    })
+    .await
+    .context("Failed to fetch data")?;
 
     let status = resp.status();
     if status != StatusCode::OK {
         return Err(anyhow!("AQI request failed with status: {status}"));
     }
 
-    let data = resp.json::<Vec<AQIData>>().await.context("Failed to decode JSON")?;
+    let bytes = resp.bytes().await.context("Failed to read response body")?;
+    let text = decode_response_body(&bytes);
+    let data = serde_json::from_str::<Vec<AQIData>>(&text).context("Failed to decode JSON")?;
     Ok(data)
 }
 
@@ -181,7 +614,7 @@ fn is_missing(value: &Option<String>) -> bool {
         None => true,
         Some(v) => {
             let v = v.trim();
-            v.is_empty() || v == "â€”"
+            v.is_empty() || v == "—"
         }
     }
 }
@@ -232,9 +665,55 @@ fn get_missing_factors(station: &AQIData) -> Vec<&'static str> {
 
 fn format_missing_factors(factors: &[&'static str]) -> String {
     if factors.is_empty() {
-        return "æ— ".to_string();
+        return "无".to_string();
     }
-    factors.join("ã€")
+    factors.join("、")
+}
+
+fn parse_numeric(value: &Option<String>) -> Option<f64> {
+    value.as_deref().map(str::trim).and_then(|v| v.parse().ok())
+}
+
+/// Finds the pollutant that exceeds its configured threshold by the
+/// largest margin (as a fraction of the limit), if any does.
+fn worst_exceedance(station: &AQIData, thresholds: &Thresholds) -> Option<StationExceedance> {
+    let factors: [(&'static str, Option<f64>, f64); 7] = [
+        ("AQI", parse_numeric(&station.aqi), thresholds.aqi),
+        ("PM2.5", parse_numeric(&station.pm25), thresholds.pm25),
+        ("PM10", parse_numeric(&station.pm10), thresholds.pm10),
+        ("O3", parse_numeric(&station.o3), thresholds.o3),
+        ("NO2", parse_numeric(&station.no2), thresholds.no2),
+        ("SO2", parse_numeric(&station.so2), thresholds.so2),
+        ("CO", parse_numeric(&station.co), thresholds.co),
+    ];
+
+    let (factor, value, limit) = factors
+        .into_iter()
+        .filter_map(|(factor, value, limit)| value.map(|value| (factor, value, limit)))
+        .filter(|(_, value, limit)| *value > *limit)
+        .max_by(|(_, a_value, a_limit), (_, b_value, b_limit)| {
+            (a_value / a_limit)
+                .partial_cmp(&(b_value / b_limit))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+
+    Some(StationExceedance {
+        station_name: station
+            .position_name
+            .as_deref()
+            .unwrap_or("Unknown")
+            .trim()
+            .to_string(),
+        quality: station
+            .quality
+            .as_deref()
+            .unwrap_or("Unknown")
+            .trim()
+            .to_string(),
+        factor,
+        value,
+        limit,
+    })
 }
 
 fn format_time(problem_stations: &[AQIData]) -> String {
@@ -248,44 +727,303 @@ fn format_time(problem_stations: &[AQIData]) -> String {
     };
 
     match DateTime::parse_from_rfc3339(tp) {
-        Ok(dt) => dt.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string(),
+        Ok(dt) => dt
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
         Err(_) => tp.to_string(),
     }
 }
 
+fn now_epoch() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn load_alert_state(state_path: &PathBuf) -> AlertState {
+    let Ok(file) = File::open(state_path) else {
+        return AlertState::default();
+    };
+    serde_json::from_reader(BufReader::new(file)).unwrap_or_default()
+}
+
+fn save_alert_state(state_path: &PathBuf, state: &AlertState) -> Result<()> {
+    let file = File::create(state_path)
+        .with_context(|| format!("Failed to write state file at: {}", state_path.display()))?;
+    serde_json::to_writer_pretty(file, state).context("Failed to serialize alert state")?;
+    Ok(())
+}
+
+/// Splits `active_names` into the subset that should actually trigger a
+/// notification this pass (newly alerting, or past the re-notify cooldown).
+/// Names present in `tracked` but no longer in `active_names` are returned
+/// separately as "recovered" so a recovery message can be sent. Does not
+/// mutate `tracked` — callers must call `commit_notified`/`commit_recovered`
+/// once they know a notification actually went out, so a total send
+/// failure doesn't get recorded as delivered. Shared by
+/// `reconcile_alert_state` (missing-data stations) and
+/// `reconcile_exceedance_state` (threshold exceedances).
+fn reconcile_state(
+    tracked: &HashMap<String, StationAlertState>,
+    active_names: &[String],
+    cooldown_secs: u64,
+) -> (Vec<String>, Vec<String>) {
+    let now = now_epoch();
+
+    let recovered: Vec<String> = tracked
+        .keys()
+        .filter(|name| !active_names.contains(name))
+        .cloned()
+        .collect();
+
+    let to_notify = active_names
+        .iter()
+        .filter(|name| match tracked.get(*name) {
+            None => true,
+            Some(existing) => now.saturating_sub(existing.last_notified_epoch) >= cooldown_secs,
+        })
+        .cloned()
+        .collect();
+
+    (to_notify, recovered)
+}
+
+/// Marks `names` as notified as of now. Call only after a send actually
+/// succeeded, so a total failure doesn't suppress the next retry.
+fn commit_notified(tracked: &mut HashMap<String, StationAlertState>, names: &[String]) {
+    let now = now_epoch();
+    for name in names {
+        tracked.insert(
+            name.clone(),
+            StationAlertState {
+                last_notified_epoch: now,
+            },
+        );
+    }
+}
+
+/// Drops `names` from `tracked`. Call only after the matching recovery
+/// notice actually succeeded, so a total failure doesn't lose the
+/// recovery notice permanently.
+fn commit_recovered(tracked: &mut HashMap<String, StationAlertState>, names: &[String]) {
+    for name in names {
+        tracked.remove(name);
+    }
+}
+
+/// Splits the currently-problematic stations into the subset that should
+/// actually trigger a notification this pass. Stations present in `state`
+/// but no longer problematic are returned separately as "recovered" so a
+/// recovery message can be sent.
+fn reconcile_alert_state(
+    state: &AlertState,
+    problem_stations: &[AQIData],
+    cooldown_secs: u64,
+) -> (Vec<AQIData>, Vec<String>) {
+    let problem_names: Vec<String> = problem_stations
+        .iter()
+        .filter_map(|s| s.position_name.as_deref())
+        .map(|n| n.trim().to_string())
+        .collect();
+
+    let (notify_names, recovered) = reconcile_state(&state.stations, &problem_names, cooldown_secs);
+
+    let to_notify = problem_stations
+        .iter()
+        .filter(|station| {
+            station
+                .position_name
+                .as_deref()
+                .map(|n| notify_names.contains(&n.trim().to_string()))
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect();
+
+    (to_notify, recovered)
+}
+
+/// Same as `reconcile_alert_state`, but for threshold exceedances. Each
+/// station has at most one tracked exceedance (the worst factor, see
+/// `worst_exceedance`), so the station name alone is a stable dedup key.
+fn reconcile_exceedance_state(
+    state: &AlertState,
+    exceedances: &[StationExceedance],
+    cooldown_secs: u64,
+) -> (Vec<StationExceedance>, Vec<String>) {
+    let active_names: Vec<String> = exceedances.iter().map(|e| e.station_name.clone()).collect();
+
+    let (notify_names, recovered) = reconcile_state(&state.exceeding, &active_names, cooldown_secs);
+
+    let to_notify = exceedances
+        .iter()
+        .filter(|e| notify_names.contains(&e.station_name))
+        .cloned()
+        .collect();
+
+    (to_notify, recovered)
+}
+
+async fn send_recovery_to_wechat_work(
+    client: &reqwest::Client,
+    report: &RecoveryReport,
+    webhook_key: &str,
+) -> Result<()> {
+    if report.is_empty() || webhook_key.trim().is_empty() {
+        return Ok(());
+    }
+
+    let mut markdown = "## ✅ 广州市空气质量监测站点数据已恢复\n".to_string();
+
+    if !report.recovered_stations.is_empty() {
+        markdown.push_str("以下站点的数据缺失问题已恢复正常：\n\n");
+        for name in &report.recovered_stations {
+            markdown.push_str(&format!("- **{}**\n", name));
+        }
+    }
+
+    if !report.recovered_exceedances.is_empty() {
+        markdown.push_str("以下站点已恢复到限值以内：\n\n");
+        for name in &report.recovered_exceedances {
+            markdown.push_str(&format!("- **{}**\n", name));
+        }
+    }
+
+    let webhook_url = format!(
+        "https://qyapi.weixin.qq.com/cgi-bin/webhook/send?key={}",
+        webhook_key.trim()
+    );
+    let payload = WechatWorkWebhook {
+        msg_type: "markdown".to_string(),
+        markdown: Some(WechatMarkdownContent { content: markdown }),
+    };
+
+    let resp = send_with_retry(client, &webhook_url, |url| {
+        client
+            .post(url)
+            .json(&payload)
+            .timeout(Duration::from_secs(30))
+    })
+    .await
+    .context("Failed to send WeChat Work recovery webhook")?;
+
+    if resp.status() != StatusCode::OK {
+        return Err(anyhow!(
+            "wechat work recovery webhook request failed with status: {}",
+            resp.status()
+        ));
+    }
+
+    Ok(())
+}
+
+async fn send_recovery_to_dingtalk(
+    client: &reqwest::Client,
+    report: &RecoveryReport,
+    access_token: &str,
+) -> Result<()> {
+    if report.is_empty() || access_token.trim().is_empty() {
+        return Ok(());
+    }
+
+    let title = "广州市空气质量监测站点数据已恢复".to_string();
+    let mut text = "### ✅ 广州市空气质量监测站点数据已恢复\n".to_string();
+
+    if !report.recovered_stations.is_empty() {
+        text.push_str("以下站点的数据缺失问题已恢复正常：\n\n");
+        for name in &report.recovered_stations {
+            text.push_str(&format!("- **{}**\n", name));
+        }
+    }
+
+    if !report.recovered_exceedances.is_empty() {
+        text.push_str("以下站点已恢复到限值以内：\n\n");
+        for name in &report.recovered_exceedances {
+            text.push_str(&format!("- **{}**\n", name));
+        }
+    }
+
+    let webhook_url = format!(
+        "https://oapi.dingtalk.com/robot/send?access_token={}",
+        access_token.trim()
+    );
+    let payload = DingTalkWebhook {
+        msg_type: "markdown".to_string(),
+        markdown: DingTalkMarkdown { title, text },
+        at: Some(DingTalkAt {
+            at_mobiles: Vec::new(),
+            at_user_ids: Vec::new(),
+            is_at_all: false,
+        }),
+    };
+
+    let resp = send_with_retry(client, &webhook_url, |url| {
+        client
+            .post(url)
+            .json(&payload)
+            .timeout(Duration::from_secs(30))
+    })
+    .await
+    .context("Failed to send DingTalk recovery webhook")?;
+
+    if resp.status() != StatusCode::OK {
+        return Err(anyhow!(
+            "dingtalk recovery webhook request failed with status: {}",
+            resp.status()
+        ));
+    }
+
+    Ok(())
+}
+
 async fn send_alert_to_wechat_work(
     client: &reqwest::Client,
-    problem_stations: &[AQIData],
+    report: &AlertReport,
     webhook_key: &str,
 ) -> Result<()> {
-    if problem_stations.is_empty() {
+    if report.problem_stations.is_empty() && report.exceedances.is_empty() {
         return Ok(());
     }
     if webhook_key.trim().is_empty() {
         return Ok(());
     }
 
-    let formatted_time = format_time(problem_stations);
     let mut markdown = format!(
-        "## ğŸš¨ å¹¿å·å¸‚ç©ºæ°”è´¨é‡ç›‘æµ‹ç«™ç‚¹æ•°æ®å¼‚å¸¸è­¦æŠ¥({})\n",
-        formatted_time
+        "## 🚨 广州市空气质量监测站点数据异常警报({})\n",
+        report.formatted_time
     );
-    markdown.push_str("ä»¥ä¸‹ç«™ç‚¹å­˜åœ¨æ•°æ®ç¼ºå¤±é—®é¢˜ï¼Œè¯·åŠæ—¶å…³æ³¨ï¼š\n\n");
 
-    for station in problem_stations {
-        let name = station
-            .position_name
-            .as_deref()
-            .unwrap_or("Unknown")
-            .trim();
-        let missing = get_missing_factors(station);
-        markdown.push_str(&format!(
-            "**{}**\n<font color=\"warning\">ç¼ºå¤±å› å­: {}</font>\n\n",
-            name,
-            format_missing_factors(&missing)
-        ));
+    if !report.problem_stations.is_empty() {
+        markdown.push_str("以下站点存在数据缺失问题，请及时关注：\n\n");
+        for station in &report.problem_stations {
+            let name = station.position_name.as_deref().unwrap_or("Unknown").trim();
+            let missing = get_missing_factors(station);
+            markdown.push_str(&format!(
+                "**{}**\n<font color=\"warning\">缺失因子: {}</font>\n\n",
+                name,
+                format_missing_factors(&missing)
+            ));
+        }
+        markdown.push_str(
+            "> 请相关技术人员尽快检查设备状态和数据传输链路。（缺失数据基于总站发布平台）\n\n",
+        );
+    }
+
+    if !report.exceedances.is_empty() {
+        markdown.push_str("### ⚠️ 空气质量超标站点\n\n");
+        for exceedance in &report.exceedances {
+            markdown.push_str(&format!(
+                "**{}**（{}）\n<font color=\"warning\">{} = {:.1}，超过限值 {:.1}</font>\n\n",
+                exceedance.station_name,
+                exceedance.quality,
+                exceedance.factor,
+                exceedance.value,
+                exceedance.limit
+            ));
+        }
     }
-    markdown.push_str("> è¯·ç›¸å…³æŠ€æœ¯äººå‘˜å°½å¿«æ£€æŸ¥è®¾å¤‡çŠ¶æ€å’Œæ•°æ®ä¼ è¾“é“¾è·¯ã€‚ï¼ˆç¼ºå¤±æ•°æ®åŸºäºæ€»ç«™å‘å¸ƒå¹³å°ï¼‰");
 
     let webhook_url = format!(
         "https://qyapi.weixin.qq.com/cgi-bin/webhook/send?key={}",
@@ -296,13 +1034,14 @@ async fn send_alert_to_wechat_work(
         markdown: Some(WechatMarkdownContent { content: markdown }),
     };
 
-    let resp = client
-        .post(webhook_url)
-        .json(&payload)
-        .timeout(Duration::from_secs(30))
-        .send()
-        .await
-        .context("Failed to send WeChat Work webhook")?;
+    let resp = send_with_retry(client, &webhook_url, |url| {
+        client
+            .post(url)
+            .json(&payload)
+            .timeout(Duration::from_secs(30))
+    })
+    .await
+    .context("Failed to send WeChat Work webhook")?;
 
     if resp.status() != StatusCode::OK {
         return Err(anyhow!(
@@ -316,37 +1055,53 @@ async fn send_alert_to_wechat_work(
 
 async fn send_alert_to_dingtalk(
     client: &reqwest::Client,
-    problem_stations: &[AQIData],
+    report: &AlertReport,
     access_token: &str,
 ) -> Result<()> {
-    if problem_stations.is_empty() {
+    if report.problem_stations.is_empty() && report.exceedances.is_empty() {
         return Ok(());
     }
     if access_token.trim().is_empty() {
         return Ok(());
     }
 
-    let formatted_time = format_time(problem_stations);
-    let title = format!("å¹¿å·å¸‚ç©ºæ°”è´¨é‡ç›‘æµ‹ç«™ç‚¹æ•°æ®å¼‚å¸¸è­¦æŠ¥({})", formatted_time);
+    let title = format!(
+        "广州市空气质量监测站点数据异常警报({})",
+        report.formatted_time
+    );
 
-    let mut text = "### ğŸš¨ å¹¿å·å¸‚ç©ºæ°”è´¨é‡ç›‘æµ‹ç«™ç‚¹æ•°æ®å¼‚å¸¸è­¦æŠ¥\n".to_string();
-    text.push_str(&format!("#### {}\n", formatted_time));
-    text.push_str("ä»¥ä¸‹ç«™ç‚¹å­˜åœ¨æ•°æ®ç¼ºå¤±é—®é¢˜ï¼Œè¯·åŠæ—¶å…³æ³¨ï¼š\n\n");
+    let mut text = "### 🚨 广州市空气质量监测站点数据异常警报\n".to_string();
+    text.push_str(&format!("#### {}\n", report.formatted_time));
 
-    for station in problem_stations {
-        let name = station
-            .position_name
-            .as_deref()
-            .unwrap_or("Unknown")
-            .trim();
-        let missing = get_missing_factors(station);
-        text.push_str(&format!(
-            "- **{}**\n  - ç¼ºå¤±å› å­: {}\n\n",
-            name,
-            format_missing_factors(&missing)
-        ));
+    if !report.problem_stations.is_empty() {
+        text.push_str("以下站点存在数据缺失问题，请及时关注：\n\n");
+        for station in &report.problem_stations {
+            let name = station.position_name.as_deref().unwrap_or("Unknown").trim();
+            let missing = get_missing_factors(station);
+            text.push_str(&format!(
+                "- **{}**\n  - 缺失因子: {}\n\n",
+                name,
+                format_missing_factors(&missing)
+            ));
+        }
+        text.push_str(
+            "> 请相关技术人员尽快检查设备状态和数据传输链路。（缺失数据基于总站发布平台）\n\n",
+        );
+    }
+
+    if !report.exceedances.is_empty() {
+        text.push_str("#### 超标站点\n\n");
+        for exceedance in &report.exceedances {
+            text.push_str(&format!(
+                "- **{}**（{}）\n  - {} = {:.1}，超过限值 {:.1}\n\n",
+                exceedance.station_name,
+                exceedance.quality,
+                exceedance.factor,
+                exceedance.value,
+                exceedance.limit
+            ));
+        }
     }
-    text.push_str("> è¯·ç›¸å…³æŠ€æœ¯äººå‘˜å°½å¿«æ£€æŸ¥è®¾å¤‡çŠ¶æ€å’Œæ•°æ®ä¼ è¾“é“¾è·¯ã€‚ï¼ˆç¼ºå¤±æ•°æ®åŸºäºæ€»ç«™å‘å¸ƒå¹³å°ï¼‰");
 
     let webhook_url = format!(
         "https://oapi.dingtalk.com/robot/send?access_token={}",
@@ -362,13 +1117,14 @@ async fn send_alert_to_dingtalk(
         }),
     };
 
-    let resp = client
-        .post(webhook_url)
-        .json(&payload)
-        .timeout(Duration::from_secs(30))
-        .send()
-        .await
-        .context("Failed to send DingTalk webhook")?;
+    let resp = send_with_retry(client, &webhook_url, |url| {
+        client
+            .post(url)
+            .json(&payload)
+            .timeout(Duration::from_secs(30))
+    })
+    .await
+    .context("Failed to send DingTalk webhook")?;
 
     if resp.status() != StatusCode::OK {
         return Err(anyhow!(
@@ -380,37 +1136,480 @@ async fn send_alert_to_dingtalk(
     Ok(())
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let config = get_config()?;
-    let client = reqwest::Client::new();
+#[derive(Debug, Serialize)]
+struct TelegramSendMessage {
+    chat_id: String,
+    text: String,
+    parse_mode: String,
+}
+
+async fn send_alert_to_telegram(
+    client: &reqwest::Client,
+    report: &AlertReport,
+    bot_token: &str,
+    chat_id: &str,
+) -> Result<()> {
+    if (report.problem_stations.is_empty() && report.exceedances.is_empty())
+        || bot_token.trim().is_empty()
+        || chat_id.trim().is_empty()
+    {
+        return Ok(());
+    }
+
+    let mut text = format!(
+        "*🚨 广州市空气质量监测站点数据异常警报({})*\n",
+        report.formatted_time
+    );
+
+    if !report.problem_stations.is_empty() {
+        text.push_str("以下站点存在数据缺失问题，请及时关注：\n\n");
+        for station in &report.problem_stations {
+            let name = station.position_name.as_deref().unwrap_or("Unknown").trim();
+            let missing = get_missing_factors(station);
+            text.push_str(&format!(
+                "*{}*\n缺失因子: {}\n\n",
+                name,
+                format_missing_factors(&missing)
+            ));
+        }
+    }
+
+    if !report.exceedances.is_empty() {
+        text.push_str("*超标站点*\n\n");
+        for exceedance in &report.exceedances {
+            text.push_str(&format!(
+                "*{}*（{}）\n{} = {:.1}，超过限值 {:.1}\n\n",
+                exceedance.station_name,
+                exceedance.quality,
+                exceedance.factor,
+                exceedance.value,
+                exceedance.limit
+            ));
+        }
+    }
+
+    let send_url = format!(
+        "https://api.telegram.org/bot{}/sendMessage",
+        bot_token.trim()
+    );
+    let payload = TelegramSendMessage {
+        chat_id: chat_id.trim().to_string(),
+        text,
+        parse_mode: "Markdown".to_string(),
+    };
+
+    let resp = send_with_retry(client, &send_url, |url| {
+        client
+            .post(url)
+            .json(&payload)
+            .timeout(Duration::from_secs(30))
+    })
+    .await
+    .context("Failed to send Telegram message")?;
+
+    if resp.status() != StatusCode::OK {
+        return Err(anyhow!(
+            "telegram sendMessage request failed with status: {}",
+            resp.status()
+        ));
+    }
+
+    Ok(())
+}
+
+async fn send_recovery_to_telegram(
+    client: &reqwest::Client,
+    report: &RecoveryReport,
+    bot_token: &str,
+    chat_id: &str,
+) -> Result<()> {
+    if report.is_empty() || bot_token.trim().is_empty() || chat_id.trim().is_empty() {
+        return Ok(());
+    }
+
+    let mut text = "*✅ 广州市空气质量监测站点数据已恢复*\n".to_string();
+
+    if !report.recovered_stations.is_empty() {
+        text.push_str("以下站点的数据缺失问题已恢复正常：\n\n");
+        for name in &report.recovered_stations {
+            text.push_str(&format!("*{}*\n", name));
+        }
+    }
+
+    if !report.recovered_exceedances.is_empty() {
+        text.push_str("以下站点已恢复到限值以内：\n\n");
+        for name in &report.recovered_exceedances {
+            text.push_str(&format!("*{}*\n", name));
+        }
+    }
+
+    let send_url = format!(
+        "https://api.telegram.org/bot{}/sendMessage",
+        bot_token.trim()
+    );
+    let payload = TelegramSendMessage {
+        chat_id: chat_id.trim().to_string(),
+        text,
+        parse_mode: "Markdown".to_string(),
+    };
+
+    let resp = send_with_retry(client, &send_url, |url| {
+        client
+            .post(url)
+            .json(&payload)
+            .timeout(Duration::from_secs(30))
+    })
+    .await
+    .context("Failed to send Telegram recovery message")?;
+
+    if resp.status() != StatusCode::OK {
+        return Err(anyhow!(
+            "telegram recovery sendMessage request failed with status: {}",
+            resp.status()
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct MessageQueueStationAlert {
+    name: String,
+    missing_factors: Vec<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+struct MessageQueueExceedanceAlert {
+    station_name: String,
+    quality: String,
+    factor: &'static str,
+    value: f64,
+    limit: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct MessageQueueAlert {
+    topic: String,
+    formatted_time: String,
+    stations: Vec<MessageQueueStationAlert>,
+    exceedances: Vec<MessageQueueExceedanceAlert>,
+}
 
-    let data = fetch_aqi_data(&client).await?;
-    let problem_stations: Vec<AQIData> = data
+/// Publishes an alert to `broker_url` as an HTTP POST of JSON (topic is
+/// carried in the body, not a transport concept). This is an HTTP webhook
+/// sink, not a native client for an AMQP/Kafka/MQTT broker — `MQ_BROKER_URL`
+/// must point at an HTTP endpoint that ingests these payloads (e.g. a
+/// webhook-to-broker bridge), not the broker's own wire-protocol address.
+async fn send_alert_to_message_queue(
+    client: &reqwest::Client,
+    report: &AlertReport,
+    broker_url: &str,
+    topic: &str,
+) -> Result<()> {
+    if (report.problem_stations.is_empty() && report.exceedances.is_empty())
+        || broker_url.trim().is_empty()
+        || topic.trim().is_empty()
+    {
+        return Ok(());
+    }
+
+    let payload = MessageQueueAlert {
+        topic: topic.trim().to_string(),
+        formatted_time: report.formatted_time.clone(),
+        stations: report
+            .problem_stations
+            .iter()
+            .map(|station| MessageQueueStationAlert {
+                name: station
+                    .position_name
+                    .as_deref()
+                    .unwrap_or("Unknown")
+                    .trim()
+                    .to_string(),
+                missing_factors: get_missing_factors(station),
+            })
+            .collect(),
+        exceedances: report
+            .exceedances
+            .iter()
+            .map(|exceedance| MessageQueueExceedanceAlert {
+                station_name: exceedance.station_name.clone(),
+                quality: exceedance.quality.clone(),
+                factor: exceedance.factor,
+                value: exceedance.value,
+                limit: exceedance.limit,
+            })
+            .collect(),
+    };
+
+    let resp = send_with_retry(client, broker_url.trim(), |url| {
+        client
+            .post(url)
+            .json(&payload)
+            .timeout(Duration::from_secs(30))
+    })
+    .await
+    .context("Failed to publish alert to message queue")?;
+
+    if resp.status() != StatusCode::OK {
+        return Err(anyhow!(
+            "message queue publish failed with status: {}",
+            resp.status()
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct MessageQueueRecovery {
+    topic: String,
+    recovered_stations: Vec<String>,
+    recovered_exceedances: Vec<String>,
+}
+
+async fn send_recovery_to_message_queue(
+    client: &reqwest::Client,
+    report: &RecoveryReport,
+    broker_url: &str,
+    topic: &str,
+) -> Result<()> {
+    if report.is_empty() || broker_url.trim().is_empty() || topic.trim().is_empty() {
+        return Ok(());
+    }
+
+    let payload = MessageQueueRecovery {
+        topic: topic.trim().to_string(),
+        recovered_stations: report.recovered_stations.clone(),
+        recovered_exceedances: report.recovered_exceedances.clone(),
+    };
+
+    let resp = send_with_retry(client, broker_url.trim(), |url| {
+        client
+            .post(url)
+            .json(&payload)
+            .timeout(Duration::from_secs(30))
+    })
+    .await
+    .context("Failed to publish recovery notice to message queue")?;
+
+    if resp.status() != StatusCode::OK {
+        return Err(anyhow!(
+            "message queue recovery publish failed with status: {}",
+            resp.status()
+        ));
+    }
+
+    Ok(())
+}
+
+struct WechatWorkNotifier {
+    client: reqwest::Client,
+    webhook_key: String,
+}
+
+#[async_trait]
+impl Notifier for WechatWorkNotifier {
+    fn name(&self) -> &'static str {
+        "企业微信"
+    }
+
+    async fn send(&self, report: &AlertReport) -> Result<()> {
+        send_alert_to_wechat_work(&self.client, report, &self.webhook_key).await
+    }
+
+    async fn send_recovery(&self, report: &RecoveryReport) -> Result<()> {
+        send_recovery_to_wechat_work(&self.client, report, &self.webhook_key).await
+    }
+}
+
+struct DingTalkNotifier {
+    client: reqwest::Client,
+    access_token: String,
+}
+
+#[async_trait]
+impl Notifier for DingTalkNotifier {
+    fn name(&self) -> &'static str {
+        "钉钉"
+    }
+
+    async fn send(&self, report: &AlertReport) -> Result<()> {
+        send_alert_to_dingtalk(&self.client, report, &self.access_token).await
+    }
+
+    async fn send_recovery(&self, report: &RecoveryReport) -> Result<()> {
+        send_recovery_to_dingtalk(&self.client, report, &self.access_token).await
+    }
+}
+
+struct TelegramNotifier {
+    client: reqwest::Client,
+    bot_token: String,
+    chat_id: String,
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &'static str {
+        "Telegram"
+    }
+
+    async fn send(&self, report: &AlertReport) -> Result<()> {
+        send_alert_to_telegram(&self.client, report, &self.bot_token, &self.chat_id).await
+    }
+
+    async fn send_recovery(&self, report: &RecoveryReport) -> Result<()> {
+        send_recovery_to_telegram(&self.client, report, &self.bot_token, &self.chat_id).await
+    }
+}
+
+/// Despite the name, this is an HTTP webhook sink (see
+/// `send_alert_to_message_queue`), not a native AMQP/Kafka/MQTT client.
+struct MessageQueueNotifier {
+    client: reqwest::Client,
+    broker_url: String,
+    topic: String,
+}
+
+#[async_trait]
+impl Notifier for MessageQueueNotifier {
+    fn name(&self) -> &'static str {
+        "消息队列"
+    }
+
+    async fn send(&self, report: &AlertReport) -> Result<()> {
+        send_alert_to_message_queue(&self.client, report, &self.broker_url, &self.topic).await
+    }
+
+    async fn send_recovery(&self, report: &RecoveryReport) -> Result<()> {
+        send_recovery_to_message_queue(&self.client, report, &self.broker_url, &self.topic).await
+    }
+}
+
+async fn run_once(client: &reqwest::Client, config: &Config, state_path: &PathBuf) -> Result<()> {
+    let data = fetch_aqi_data(client).await?;
+    let candidates: Vec<AQIData> = data
         .into_iter()
         .filter(|s| !is_ignored_station(s))
+        .collect();
+
+    let problem_stations: Vec<AQIData> = candidates
+        .iter()
         .filter(|s| has_missing_data(s))
+        .cloned()
+        .collect();
+    let exceedances: Vec<StationExceedance> = candidates
+        .iter()
+        .filter_map(|s| worst_exceedance(s, &config.thresholds))
         .collect();
 
-    if problem_stations.is_empty() {
-        println!("æ‰€æœ‰ï¼ˆéå¿½ç•¥åå•ï¼‰ç«™ç‚¹æ•°æ®æ­£å¸¸");
-        return Ok(());
-    }
+    let mut state = load_alert_state(state_path);
+    let (to_notify, recovered) =
+        reconcile_alert_state(&state, &problem_stations, config.renotify_cooldown_secs);
+    let (exceedances_to_notify, recovered_exceedances) =
+        reconcile_exceedance_state(&state, &exceedances, config.renotify_cooldown_secs);
 
-    if !config.webhook_key.is_empty() {
-        match send_alert_to_wechat_work(&client, &problem_stations, &config.webhook_key).await {
-            Ok(()) => println!("å·²æˆåŠŸå‘é€è­¦æŠ¥åˆ°ä¼ä¸šå¾®ä¿¡"),
-            Err(err) => eprintln!("Failed to send alert to WeChat Work: {err}"),
+    let notifiers = build_notifiers(client, config);
+
+    let recovery_report = RecoveryReport::new(recovered.clone(), recovered_exceedances.clone());
+    if !recovery_report.is_empty() {
+        let mut any_sent = false;
+        for notifier in &notifiers {
+            match notifier.send_recovery(&recovery_report).await {
+                Ok(()) => {
+                    any_sent = true;
+                    println!("已成功发送恢复通知到{}", notifier.name());
+                }
+                Err(err) => {
+                    eprintln!(
+                        "Failed to send recovery notice to {}: {err}",
+                        notifier.name()
+                    )
+                }
+            }
+        }
+        if any_sent {
+            commit_recovered(&mut state.stations, &recovered);
+            commit_recovered(&mut state.exceeding, &recovered_exceedances);
+            println!(
+                "{} 个站点数据已恢复正常",
+                recovery_report.recovered_stations.len()
+                    + recovery_report.recovered_exceedances.len()
+            );
+        } else {
+            eprintln!("All recovery notifiers failed, will retry next pass");
         }
     }
 
-    if !config.dingtalk_access_token.is_empty() {
-        match send_alert_to_dingtalk(&client, &problem_stations, &config.dingtalk_access_token).await {
-            Ok(()) => println!("å·²æˆåŠŸå‘é€è­¦æŠ¥åˆ°é’‰é’‰"),
-            Err(err) => eprintln!("Failed to send alert to DingTalk: {err}"),
+    if to_notify.is_empty() && exceedances_to_notify.is_empty() {
+        println!("所有（非忽略名单）站点数据正常或已在冷却期内");
+        save_alert_state(state_path, &state)?;
+        return Ok(());
+    }
+
+    let to_notify_names: Vec<String> = to_notify
+        .iter()
+        .filter_map(|s| s.position_name.as_deref())
+        .map(|n| n.trim().to_string())
+        .collect();
+    let exceedance_names: Vec<String> = exceedances_to_notify
+        .iter()
+        .map(|e| e.station_name.clone())
+        .collect();
+
+    let mut report = AlertReport::new(to_notify, exceedances_to_notify);
+    if report.problem_stations.is_empty() {
+        report.formatted_time = format_time(&candidates);
+    }
+    let mut any_sent = false;
+    for notifier in &notifiers {
+        match notifier.send(&report).await {
+            Ok(()) => {
+                any_sent = true;
+                println!("已成功发送警报到{}", notifier.name());
+            }
+            Err(err) => eprintln!("Failed to send alert to {}: {err}", notifier.name()),
         }
     }
+    if any_sent {
+        commit_notified(&mut state.stations, &to_notify_names);
+        commit_notified(&mut state.exceeding, &exceedance_names);
+    } else {
+        eprintln!("All alert notifiers failed, will retry next pass");
+    }
+    save_alert_state(state_path, &state)?;
 
-    println!("å‘ç° {} ä¸ªå¼‚å¸¸ç«™ç‚¹ï¼ˆå·²æ’é™¤å¿½ç•¥åå•ï¼‰", problem_stations.len());
+    println!(
+        "发现 {} 个异常站点、{} 个超标站点（已排除忽略名单）",
+        report.problem_stations.len(),
+        report.exceedances.len()
+    );
     Ok(())
 }
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let options = parse_args();
+    let config = get_config()?;
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .context("Failed to build HTTP client")?;
+    let state_path = exe_dir()?.join(STATE_FILE_NAME);
+
+    if !options.watch {
+        return run_once(&client, &config, &state_path).await;
+    }
+
+    println!(
+        "进入守护进程模式，每 {} 秒轮询一次",
+        options.interval.as_secs()
+    );
+    loop {
+        if let Err(err) = run_once(&client, &config, &state_path).await {
+            eprintln!("Poll failed: {err}");
+        }
+        tokio::time::sleep(options.interval).await;
+    }
+}